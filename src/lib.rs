@@ -3,6 +3,8 @@
 
 
 const BMP_HEADER_SIZE: usize = 54;
+const BMP_V4_HEADER_SIZE: usize = 14 + 108;
+const BMP_INDEXED_HEADER_SIZE: usize = 14 + 40 + 256 * 4;
 
 
 /// Calculate the length of a BMP file of the given width and height.
@@ -22,14 +24,463 @@ pub const fn buffer_length(width: usize, height: usize) -> usize {
 	BMP_HEADER_SIZE + pixel_data_size
 }
 
+
+/// Fallibly calculate the length of a BMP file of the given width and height, returning
+/// `None` instead of silently wrapping if the calculation would overflow `usize`.
+///
+/// Prefer this over [buffer_length] when width and height come from runtime input rather
+/// than compile-time constants, since [buffer_length] being a `const fn` has no error path.
+pub fn try_buffer_length(width: usize, height: usize) -> Option<usize> {
+	let row_stride = width.checked_mul(3)?.checked_next_multiple_of(4)?;
+	let pixel_data_size = height.checked_mul(row_stride)?;
+	BMP_HEADER_SIZE.checked_add(pixel_data_size)
+}
+
+
+/// Calculate the length of a BMP file written by [write_bmp_rgba] for the given width and height.
+///
+/// Unlike the 24-bit format, 32-bit rows are already 4 byte aligned, so no row padding is needed.
+/// Is a const function so it can be used as the size of a statically sized array.
+///
+/// ```rust
+/// let mut buffer = [0u8; simple_bmp::buffer_length_rgba(100, 100)];
+/// ```
+pub const fn buffer_length_rgba(width: usize, height: usize) -> usize {
+	let pixel_data_size = width * height * 4;
+	BMP_V4_HEADER_SIZE + pixel_data_size
+}
+
+
+/// Fallibly calculate the length of a BMP file written by [write_bmp_rgba], returning
+/// `None` instead of silently wrapping if the calculation would overflow `usize`.
+///
+/// Prefer this over [buffer_length_rgba] when width and height come from runtime input
+/// rather than compile-time constants, since [buffer_length_rgba] being a `const fn` has
+/// no error path.
+pub fn try_buffer_length_rgba(width: usize, height: usize) -> Option<usize> {
+	let pixel_data_size = width.checked_mul(height)?.checked_mul(4)?;
+	BMP_V4_HEADER_SIZE.checked_add(pixel_data_size)
+}
+
+
+/// Calculate the length of a BMP file written by [write_bmp_indexed] for the given width and height.
+///
+/// Is a const function so it can be used as the size of a statically sized array.
+///
+/// ```rust
+/// let mut buffer = [0u8; simple_bmp::buffer_length_indexed(100, 100)];
+/// ```
+pub const fn buffer_length_indexed(width: usize, height: usize) -> usize {
+	let row_stride = width.next_multiple_of(4);
+	let pixel_data_size = height * row_stride;
+	BMP_INDEXED_HEADER_SIZE + pixel_data_size
+}
+
+
+/// Fallibly calculate the length of a BMP file written by [write_bmp_indexed], returning
+/// `None` instead of silently wrapping if the calculation would overflow `usize`.
+pub fn try_buffer_length_indexed(width: usize, height: usize) -> Option<usize> {
+	let row_stride = width.checked_next_multiple_of(4)?;
+	let pixel_data_size = height.checked_mul(row_stride)?;
+	BMP_INDEXED_HEADER_SIZE.checked_add(pixel_data_size)
+}
+
+
+/// Calculate the worst-case length of a BMP file written by [write_bmp_indexed_rle] for the
+/// given width and height, for sizing a buffer before compressing.
+///
+/// RLE8 can expand pixel data that has no runs to compress: every run shorter than 3 pixels
+/// costs 2 bytes per pixel (an encoded `(1, value)` pair for each pixel — absolute-mode
+/// counts of 0, 1, and 2 are reserved for the end-of-line/end-of-bitmap/delta escapes), and
+/// no other encoding choice this crate makes costs more than that per pixel. So every row
+/// can cost up to 2 bytes per pixel plus a 2 byte end-of-line marker. The actual compressed
+/// output is usually much smaller than this bound. Is a const function so it can be used as
+/// the size of a statically sized array.
+pub const fn buffer_length_indexed_rle_worst_case(width: usize, height: usize) -> usize {
+	let row_worst_case = width * 2 + 2;
+	BMP_INDEXED_HEADER_SIZE + height * row_worst_case + 2
+}
+
+/// The X/Y pixels-per-meter resolution written into a BMP's header.
+///
+/// Defaults to 1000 pixels per meter (roughly 25 dpi) on both axes, matching the
+/// resolution this crate wrote before this option existed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Resolution {
+	pub x_pixels_per_meter: u32,
+	pub y_pixels_per_meter: u32,
+}
+
+impl Default for Resolution {
+	fn default() -> Self {
+		Resolution { x_pixels_per_meter: 1000, y_pixels_per_meter: 1000 }
+	}
+}
+
+impl Resolution {
+	/// Build a [Resolution] from a dpi value, converting to pixels-per-meter via the
+	/// standard 1 inch = 0.0254 meter, and applying it to both axes.
+	pub fn from_dpi(dpi: u32) -> Self {
+		let pixels_per_meter = (dpi as f64 / 0.0254) as u32;
+		Resolution { x_pixels_per_meter: pixels_per_meter, y_pixels_per_meter: pixels_per_meter }
+	}
+}
+
+
 /// Write a valid BMP file into the provided buffer, returning the number of bytes written.
 ///
+/// Rows are stored bottom-up, as is conventional for BMP. Use [write_bmp_topdown] if your
+/// pixel data is already in top-down row order and you'd like to avoid the flip, or
+/// [write_bmp_with_resolution] to control the physical-size metadata in the header.
+///
 /// The buffer can be longer than required. Extra space will remain untouched.
 /// See documentation on the [Error] enum for possible errors.
 pub fn write_bmp(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8]) -> Result<usize, Error> {
+	write_bmp_impl(buffer, width, height, pixels, false, Resolution::default())
+}
+
+
+/// Write a valid BMP file into the provided buffer, storing rows top-down instead of the
+/// conventional bottom-up order, and returning the number of bytes written.
+///
+/// This is signaled to readers with a negative `biHeight`, as supported by decoders such
+/// as the one in the `image` crate. Rows are copied directly, without the vertical flip
+/// [write_bmp] performs.
+///
+/// The buffer can be longer than required. Extra space will remain untouched.
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_topdown(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8]) -> Result<usize, Error> {
+	write_bmp_impl(buffer, width, height, pixels, true, Resolution::default())
+}
+
+
+/// Write a valid BMP file into the provided buffer with a caller-specified [Resolution],
+/// returning the number of bytes written. Rows are stored bottom-up; see [write_bmp].
+///
+/// The buffer can be longer than required. Extra space will remain untouched.
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_with_resolution(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8], resolution: Resolution) -> Result<usize, Error> {
+	write_bmp_impl(buffer, width, height, pixels, false, resolution)
+}
+
+
+/// Write a valid BMP file into the provided buffer with a caller-specified [Resolution],
+/// storing rows top-down instead of the conventional bottom-up order, and returning the
+/// number of bytes written. See [write_bmp_topdown].
+///
+/// The buffer can be longer than required. Extra space will remain untouched.
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_topdown_with_resolution(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8], resolution: Resolution) -> Result<usize, Error> {
+	write_bmp_impl(buffer, width, height, pixels, true, resolution)
+}
+
+
+/// Write a valid 32 bit-per-pixel BGRA BMP file into the provided buffer, returning the
+/// number of bytes written.
+///
+/// This writes a `BITMAPV4HEADER` with `biCompression = BI_BITFIELDS` and explicit channel
+/// masks instead of the 40 byte `BITMAPINFOHEADER` [write_bmp] uses, so the alpha channel
+/// survives in readers that understand it (Windows, browsers). `pixels` must be exactly
+/// `width * height * 4` bytes of top-down BGRA data, which is stored bottom-up like [write_bmp];
+/// 32-bit rows are already 4 byte aligned,
+/// so unlike [write_bmp] there is no row padding to account for. Use [buffer_length_rgba] to
+/// size the buffer.
+///
+/// The buffer can be longer than required. Extra space will remain untouched.
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_rgba(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8]) -> Result<usize, Error> {
+	let file_length = try_buffer_length_rgba(width, height).ok_or(Error::DimensionsOverflow { width, height })?;
+	let pixel_data_size = file_length - BMP_V4_HEADER_SIZE;
+
+	if (i32::MAX as usize) < width {
+		return Err(Error::WidthTooLarge { max: i32::MAX as usize, was: width });
+	}
+
+	if (i32::MAX as usize) < height {
+		return Err(Error::HeightTooLarge { max: i32::MAX as usize, was: height });
+	}
+
+	if (u32::MAX as usize) < file_length {
+		return Err(Error::FileLengthTooLong { max: u32::MAX as usize, would_be: file_length });
+	}
+
+	if pixels.len() != width * height * 4 {
+		return Err(Error::BadPixelDataLength { expected: width * height * 4, was: pixels.len() });
+	}
+
+	if buffer.len() < file_length {
+		return Err(Error::BufferTooSmall { required: file_length, was: buffer.len() });
+	}
+
+	// File header
+	buffer[0..2].copy_from_slice(b"BM");
+	buffer[2..][..4].copy_from_slice(&(file_length as u32).to_le_bytes());
+	buffer[6..][..4].fill(0);
+	buffer[10..][..4].copy_from_slice(&(BMP_V4_HEADER_SIZE as u32).to_le_bytes());
+
+	// BITMAPV4HEADER
+	buffer[14..][..4].copy_from_slice(&108u32.to_le_bytes());
+	buffer[18..][..4].copy_from_slice(&(width as i32).to_le_bytes());
+	buffer[22..][..4].copy_from_slice(&(height as i32).to_le_bytes());
+	buffer[26..][..2].copy_from_slice(&1u16.to_le_bytes());
+	buffer[28..][..2].copy_from_slice(&32u16.to_le_bytes());
+	buffer[30..][..4].copy_from_slice(&3u32.to_le_bytes()); // BI_BITFIELDS
+	buffer[34..][..4].copy_from_slice(&(pixel_data_size as u32).to_le_bytes());
+	buffer[38..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[42..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[46..][..4].copy_from_slice(&0u32.to_le_bytes());
+	buffer[50..][..4].copy_from_slice(&0u32.to_le_bytes());
+	buffer[54..][..4].copy_from_slice(&0x00FF0000u32.to_le_bytes()); // Red mask
+	buffer[58..][..4].copy_from_slice(&0x0000FF00u32.to_le_bytes()); // Green mask
+	buffer[62..][..4].copy_from_slice(&0x000000FFu32.to_le_bytes()); // Blue mask
+	buffer[66..][..4].copy_from_slice(&0xFF000000u32.to_le_bytes()); // Alpha mask
+	buffer[70..][..52].fill(0); // CSType, endpoints, and gamma are unused for BI_BITFIELDS
+
+	// Pixel data
+	for row in 0..height {
+		let dst_begin = BMP_V4_HEADER_SIZE + width * 4 * row;
+		let dst_end = dst_begin + width * 4;
+		let src_begin = (height - row - 1) * width * 4;
+		let src_end = src_begin + width * 4;
+		buffer[dst_begin..dst_end].copy_from_slice(&pixels[src_begin..src_end]);
+	}
+
+	Ok(file_length)
+}
+
+
+/// A 256 entry color table, one `[red, green, blue]` triple per palette index.
+///
+/// Passed to [write_bmp_indexed] and [write_bmp_indexed_rle]. `None` auto-generates a
+/// grayscale ramp (palette index N maps to the gray value N).
+pub type Palette = [[u8; 3]; 256];
+
+
+/// Write an 8 bit-per-pixel indexed-color BMP file into the provided buffer, returning the
+/// number of bytes written.
+///
+/// `pixels` must be exactly `width * height` bytes, one palette index per pixel. If `palette`
+/// is `None`, a grayscale ramp is written so the indices can be used directly as gray values.
+/// Rows are stored bottom-up, like [write_bmp]. Use [buffer_length_indexed] to size the buffer.
+///
+/// The buffer can be longer than required. Extra space will remain untouched.
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_indexed(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8], palette: Option<&Palette>) -> Result<usize, Error> {
+	let file_length = try_buffer_length_indexed(width, height).ok_or(Error::DimensionsOverflow { width, height })?;
+	let row_stride = width.next_multiple_of(4);
+	let pixel_data_size = file_length - BMP_INDEXED_HEADER_SIZE;
+
+	if (i32::MAX as usize) < width {
+		return Err(Error::WidthTooLarge { max: i32::MAX as usize, was: width });
+	}
+
+	if (i32::MAX as usize) < height {
+		return Err(Error::HeightTooLarge { max: i32::MAX as usize, was: height });
+	}
+
+	if (u32::MAX as usize) < file_length {
+		return Err(Error::FileLengthTooLong { max: u32::MAX as usize, would_be: file_length });
+	}
+
+	if pixels.len() != width * height {
+		return Err(Error::BadPixelDataLength { expected: width * height, was: pixels.len() });
+	}
+
+	if buffer.len() < file_length {
+		return Err(Error::BufferTooSmall { required: file_length, was: buffer.len() });
+	}
+
+	// File header
+	buffer[0..2].copy_from_slice(b"BM");
+	buffer[2..][..4].copy_from_slice(&(file_length as u32).to_le_bytes());
+	buffer[6..][..4].fill(0);
+	buffer[10..][..4].copy_from_slice(&(BMP_INDEXED_HEADER_SIZE as u32).to_le_bytes());
+
+	// DIB Header
+	buffer[14..][..4].copy_from_slice(&40u32.to_le_bytes());
+	buffer[18..][..4].copy_from_slice(&(width as i32).to_le_bytes());
+	buffer[22..][..4].copy_from_slice(&(height as i32).to_le_bytes());
+	buffer[26..][..2].copy_from_slice(&1u16.to_le_bytes());
+	buffer[28..][..2].copy_from_slice(&8u16.to_le_bytes());
+	buffer[30..][..4].copy_from_slice(&0u32.to_le_bytes());
+	buffer[34..][..4].copy_from_slice(&(pixel_data_size as u32).to_le_bytes());
+	buffer[38..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[42..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[46..][..4].copy_from_slice(&256u32.to_le_bytes());
+	buffer[50..][..4].copy_from_slice(&0u32.to_le_bytes());
+
+	// Color table
+	write_color_table(&mut buffer[54..][..1024], palette);
+
+	// Pixel data
+	for row in 0..height {
+		let dst_begin = BMP_INDEXED_HEADER_SIZE + row_stride * row;
+		let dst_end = dst_begin + width;
+		let src_begin = (height - row - 1) * width;
+		let src_end = src_begin + width;
+		buffer[dst_begin..dst_end].copy_from_slice(&pixels[src_begin..src_end]);
+		buffer[dst_end..dst_begin + row_stride].fill(0);
+	}
+
+	Ok(file_length)
+}
+
+
+/// Write an 8 bit-per-pixel indexed-color BMP file compressed with RLE8, into the provided
+/// buffer, returning the number of bytes written.
+///
+/// `pixels` must be exactly `width * height` bytes, one palette index per pixel, and is
+/// encoded bottom-up like [write_bmp_indexed]. Each row is encoded as a sequence of
+/// `(count, value)` encoded runs and absolute-mode literal runs, following the standard BMP
+/// RLE8 scheme: absolute-mode runs are `0, count` followed by `count` literal bytes padded to
+/// an even length, rows end with `0, 0`, and the bitmap ends with `0, 1`. Absolute mode is
+/// never used for runs shorter than 3 pixels, since a count of 0, 1, or 2 there would collide
+/// with those escapes; short runs are instead emitted as their own encoded pairs.
+///
+/// This can dramatically shrink output for images with large runs of repeated pixels (such as
+/// screenshots and UI renders) compared to the uncompressed path, but can also expand data that
+/// has no runs to compress. Use [buffer_length_indexed_rle_worst_case] to size the buffer.
+///
+/// See documentation on the [Error] enum for possible errors.
+pub fn write_bmp_indexed_rle(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8], palette: Option<&Palette>) -> Result<usize, Error> {
+	if (i32::MAX as usize) < width {
+		return Err(Error::WidthTooLarge { max: i32::MAX as usize, was: width });
+	}
+
+	if (i32::MAX as usize) < height {
+		return Err(Error::HeightTooLarge { max: i32::MAX as usize, was: height });
+	}
+
+	if pixels.len() != width * height {
+		return Err(Error::BadPixelDataLength { expected: width * height, was: pixels.len() });
+	}
+
+	if buffer.len() < BMP_INDEXED_HEADER_SIZE {
+		return Err(Error::BufferTooSmall { required: BMP_INDEXED_HEADER_SIZE, was: buffer.len() });
+	}
+
+	let mut pos = BMP_INDEXED_HEADER_SIZE;
+
+	for row in 0..height {
+		let src_row = height - row - 1;
+		rle_encode_row(&pixels[src_row * width..][..width], buffer, &mut pos)?;
+
+		let is_last_row = row == height - 1;
+		push_bytes(buffer, &mut pos, if is_last_row { &[0, 1] } else { &[0, 0] })?;
+	}
+
+	let file_length = pos;
+	if (u32::MAX as usize) < file_length {
+		return Err(Error::FileLengthTooLong { max: u32::MAX as usize, would_be: file_length });
+	}
+
+	let pixel_data_size = file_length - BMP_INDEXED_HEADER_SIZE;
+
+	// File header
+	buffer[0..2].copy_from_slice(b"BM");
+	buffer[2..][..4].copy_from_slice(&(file_length as u32).to_le_bytes());
+	buffer[6..][..4].fill(0);
+	buffer[10..][..4].copy_from_slice(&(BMP_INDEXED_HEADER_SIZE as u32).to_le_bytes());
+
+	// DIB Header
+	buffer[14..][..4].copy_from_slice(&40u32.to_le_bytes());
+	buffer[18..][..4].copy_from_slice(&(width as i32).to_le_bytes());
+	buffer[22..][..4].copy_from_slice(&(height as i32).to_le_bytes());
+	buffer[26..][..2].copy_from_slice(&1u16.to_le_bytes());
+	buffer[28..][..2].copy_from_slice(&8u16.to_le_bytes());
+	buffer[30..][..4].copy_from_slice(&1u32.to_le_bytes()); // BI_RLE8
+	buffer[34..][..4].copy_from_slice(&(pixel_data_size as u32).to_le_bytes());
+	buffer[38..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[42..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[46..][..4].copy_from_slice(&256u32.to_le_bytes());
+	buffer[50..][..4].copy_from_slice(&0u32.to_le_bytes());
+
+	// Color table
+	write_color_table(&mut buffer[54..][..1024], palette);
+
+	Ok(file_length)
+}
+
+
+fn write_color_table(table: &mut [u8], palette: Option<&Palette>) {
+	for index in 0..256 {
+		let [red, green, blue] = match palette {
+			Some(palette) => palette[index],
+			None => [index as u8, index as u8, index as u8],
+		};
+
+		table[index * 4] = blue;
+		table[index * 4 + 1] = green;
+		table[index * 4 + 2] = red;
+		table[index * 4 + 3] = 0;
+	}
+}
+
+
+// Encode one row of palette indices into the standard BMP RLE8 scheme: runs of 3 or more
+// repeated bytes become an encoded `(count, value)` pair. Everything else is accumulated
+// into an absolute-mode literal run, *unless* that run is shorter than 3 pixels — absolute
+// mode's count byte doubles as the end-of-line (0, 0), end-of-bitmap (0, 1), and delta (0, 2)
+// escapes, so each pixel of a 1 or 2 pixel run is instead emitted as its own encoded
+// `(1, value)` pair to avoid colliding with them.
+fn rle_encode_row(row: &[u8], buffer: &mut [u8], pos: &mut usize) -> Result<(), Error> {
+	let run_length_at = |start: usize| -> usize {
+		let value = row[start];
+		let mut length = 1;
+		while start + length < row.len() && length < 255 && row[start + length] == value {
+			length += 1;
+		}
+		length
+	};
+
+	let mut i = 0;
+	while i < row.len() {
+		let run_length = run_length_at(i);
+
+		if run_length >= 3 {
+			push_bytes(buffer, pos, &[run_length as u8, row[i]])?;
+			i += run_length;
+		} else {
+			let literal_start = i;
+			let mut literal_len = 0;
+			while i < row.len() && literal_len < 255 && run_length_at(i) < 3 {
+				i += 1;
+				literal_len += 1;
+			}
+
+			if literal_len >= 3 {
+				push_bytes(buffer, pos, &[0, literal_len as u8])?;
+				push_bytes(buffer, pos, &row[literal_start..][..literal_len])?;
+				if literal_len % 2 == 1 {
+					push_bytes(buffer, pos, &[0])?;
+				}
+			} else {
+				for &value in &row[literal_start..][..literal_len] {
+					push_bytes(buffer, pos, &[1, value])?;
+				}
+			}
+		}
+	}
+
+	Ok(())
+}
+
+
+fn push_bytes(buffer: &mut [u8], pos: &mut usize, bytes: &[u8]) -> Result<(), Error> {
+	let end = *pos + bytes.len();
+	if buffer.len() < end {
+		return Err(Error::BufferTooSmall { required: end, was: buffer.len() });
+	}
+	buffer[*pos..end].copy_from_slice(bytes);
+	*pos = end;
+	Ok(())
+}
+
+
+fn write_bmp_impl(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8], top_down: bool, resolution: Resolution) -> Result<usize, Error> {
+	let file_length = try_buffer_length(width, height).ok_or(Error::DimensionsOverflow { width, height })?;
 	let row_stride = (width * 3).next_multiple_of(4);
-	let pixel_data_size = height * row_stride;
-	let file_length = BMP_HEADER_SIZE + pixel_data_size;
+	let pixel_data_size = file_length - BMP_HEADER_SIZE;
 
 	if (i32::MAX as usize) < width {
 		return Err(Error::WidthTooLarge { max: i32::MAX as usize, was: width });
@@ -43,6 +494,8 @@ pub fn write_bmp(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8])
 		return Err(Error::FileLengthTooLong { max: u32::MAX as usize, would_be: file_length });
 	}
 
+	// width * height * 3 cannot overflow here: try_buffer_length above already proved
+	// height * row_stride fits in a usize, and row_stride is width * 3 rounded up.
 	if pixels.len() != width * height * 3 {
 		return Err(Error::BadPixelDataLength { expected: width * height * 3, was: pixels.len() });
 	}
@@ -51,6 +504,14 @@ pub fn write_bmp(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8])
 		return Err(Error::BufferTooSmall { required: file_length, was: buffer.len() });
 	}
 
+	// A negative biHeight signals top-down row order. i32::MIN has no positive counterpart,
+	// but height is already bounded to i32::MAX above, so negating it can never produce i32::MIN.
+	let stored_height = if top_down {
+		(height as i32).checked_neg().ok_or(Error::HeightTooLarge { max: i32::MAX as usize, was: height })?
+	} else {
+		height as i32
+	};
+
 	// Header
 	buffer[0..2].copy_from_slice(b"BM");
 	buffer[2..][..4].copy_from_slice(&(file_length as u32).to_le_bytes());
@@ -60,13 +521,13 @@ pub fn write_bmp(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8])
 	// DIB Header
 	buffer[14..][..4].copy_from_slice(&40u32.to_le_bytes());
 	buffer[18..][..4].copy_from_slice(&(width as i32).to_le_bytes());
-	buffer[22..][..4].copy_from_slice(&(height as i32).to_le_bytes());
+	buffer[22..][..4].copy_from_slice(&stored_height.to_le_bytes());
 	buffer[26..][..2].copy_from_slice(&1u16.to_le_bytes());
 	buffer[28..][..2].copy_from_slice(&24u16.to_le_bytes());
 	buffer[30..][..4].copy_from_slice(&0u32.to_le_bytes());
 	buffer[34..][..4].copy_from_slice(&(pixel_data_size as u32).to_le_bytes());
-	buffer[38..][..4].copy_from_slice(&1000u32.to_le_bytes());
-	buffer[42..][..4].copy_from_slice(&1000u32.to_le_bytes());
+	buffer[38..][..4].copy_from_slice(&resolution.x_pixels_per_meter.to_le_bytes());
+	buffer[42..][..4].copy_from_slice(&resolution.y_pixels_per_meter.to_le_bytes());
 	buffer[46..][..4].copy_from_slice(&0u32.to_le_bytes());
 	buffer[50..][..4].copy_from_slice(&0u32.to_le_bytes());
 
@@ -74,7 +535,8 @@ pub fn write_bmp(buffer: &mut [u8], width: usize, height: usize, pixels: &[u8])
 	for row in 0..height {
 		let dst_begin = 54 + row_stride * row;
 		let dst_end = dst_begin + width * 3;
-		let src_begin = (height - row - 1) * width * 3;
+		let src_row = if top_down { row } else { height - row - 1 };
+		let src_begin = src_row * width * 3;
 		let src_end = src_begin + width * 3;
 		buffer[dst_begin..dst_end].copy_from_slice(&pixels[src_begin..src_end]);
 	}
@@ -104,6 +566,112 @@ pub enum Error {
 	/// The BMP file format stores the height in a signed i32.
 	/// This error is returned if the given height doesn't fit in the BMP header.
 	HeightTooLarge { max: usize, was: usize },
+
+	/// Returned by [parse_header] and [read_bmp_into] if the buffer doesn't start with
+	/// a "BM" magic number and a 40 byte BITMAPINFOHEADER.
+	NotBmp,
+
+	/// Returned by [parse_header] and [read_bmp_into] if the BMP uses a `biCompression`
+	/// value other than the uncompressed `0` this crate can read.
+	UnsupportedCompression { was: u32 },
+
+	/// Returned by [parse_header] and [read_bmp_into] if the BMP's `biBitCount` isn't
+	/// 24 bits per pixel.
+	UnsupportedBitDepth { was: u16 },
+
+	/// Returned if computing the required buffer size for the given width & height
+	/// overflows a `usize` before the other size checks can even run.
+	DimensionsOverflow { width: usize, height: usize },
+}
+
+
+/// The width, height, and bit depth of a parsed BMP header.
+///
+/// Returned by [parse_header]. `height` is always the absolute row count;
+/// check the original buffer's signed `biHeight` field if you need to know whether
+/// the file stores rows top-down or bottom-up.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BmpInfo {
+	pub width: usize,
+	pub height: usize,
+	pub bit_count: u16,
+}
+
+
+/// Parse the BITMAPFILEHEADER and BITMAPINFOHEADER of a BMP byte slice, returning its
+/// width, height, and bit depth without touching the pixel data.
+///
+/// Only uncompressed, 24 bit per pixel BMPs with a 40 byte BITMAPINFOHEADER are supported.
+/// See documentation on the [Error] enum for possible errors.
+pub fn parse_header(buffer: &[u8]) -> Result<BmpInfo, Error> {
+	if buffer.len() < BMP_HEADER_SIZE {
+		return Err(Error::BufferTooSmall { required: BMP_HEADER_SIZE, was: buffer.len() });
+	}
+
+	if &buffer[0..2] != b"BM" {
+		return Err(Error::NotBmp);
+	}
+
+	let dib_header_size = u32::from_le_bytes(buffer[14..][..4].try_into().unwrap());
+	if dib_header_size != 40 {
+		return Err(Error::NotBmp);
+	}
+
+	let width = i32::from_le_bytes(buffer[18..][..4].try_into().unwrap());
+	let height = i32::from_le_bytes(buffer[22..][..4].try_into().unwrap());
+	let bit_count = u16::from_le_bytes(buffer[28..][..2].try_into().unwrap());
+	let compression = u32::from_le_bytes(buffer[30..][..4].try_into().unwrap());
+
+	if width < 0 {
+		return Err(Error::NotBmp);
+	}
+
+	if compression != 0 {
+		return Err(Error::UnsupportedCompression { was: compression });
+	}
+
+	if bit_count != 24 {
+		return Err(Error::UnsupportedBitDepth { was: bit_count });
+	}
+
+	Ok(BmpInfo { width: width as usize, height: height.unsigned_abs() as usize, bit_count })
+}
+
+
+/// Read the pixel data of a BMP byte slice into a caller-supplied top-down RGB buffer,
+/// returning the number of bytes written.
+///
+/// `out` must be at least `width * height * 3` bytes long, and receives pixel rows
+/// top-down regardless of whether the source BMP stores them bottom-up (the default)
+/// or top-down (signaled by a negative `biHeight`).
+/// See documentation on the [Error] enum for possible errors.
+pub fn read_bmp_into(buffer: &[u8], out: &mut [u8]) -> Result<usize, Error> {
+	let info = parse_header(buffer)?;
+
+	let pixel_data_start = u32::from_le_bytes(buffer[10..][..4].try_into().unwrap()) as usize;
+	let row_stride = (info.width * 3).next_multiple_of(4);
+	let pixel_data_size = info.height * row_stride;
+	let top_down = i32::from_le_bytes(buffer[22..][..4].try_into().unwrap()) < 0;
+
+	if buffer.len() < pixel_data_start + pixel_data_size {
+		return Err(Error::BufferTooSmall { required: pixel_data_start + pixel_data_size, was: buffer.len() });
+	}
+
+	let out_len = info.width * info.height * 3;
+	if out.len() < out_len {
+		return Err(Error::BufferTooSmall { required: out_len, was: out.len() });
+	}
+
+	for row in 0..info.height {
+		let src_row = if top_down { row } else { info.height - row - 1 };
+		let src_begin = pixel_data_start + src_row * row_stride;
+		let src_end = src_begin + info.width * 3;
+		let dst_begin = row * info.width * 3;
+		let dst_end = dst_begin + info.width * 3;
+		out[dst_begin..dst_end].copy_from_slice(&buffer[src_begin..src_end]);
+	}
+
+	Ok(out_len)
 }
 
 
@@ -177,4 +745,287 @@ mod tests {
 			_ => assert!(false),
 		}
 	}
+
+	#[test]
+	fn write_indexed_grayscale_ramp() {
+		const WIDTH: usize = 4;
+		const HEIGHT: usize = 2;
+
+		let pixels = [0u8, 1, 2, 3, 4, 5, 6, 7];
+		let mut buffer = [0u8; buffer_length_indexed(WIDTH, HEIGHT)];
+		let file_length = write_bmp_indexed(&mut buffer, WIDTH, HEIGHT, &pixels, None).unwrap();
+		assert_eq!(file_length, buffer.len());
+
+		let bit_count = u16::from_le_bytes(buffer[28..][..2].try_into().unwrap());
+		assert_eq!(bit_count, 8);
+
+		// Color table entry 5 should be the grayscale ramp value (5, 5, 5) stored as BGR0.
+		let entry = &buffer[54 + 5 * 4..][..4];
+		assert_eq!(entry, [5, 5, 5, 0]);
+
+		// Bottom-up: the first pixel row in the file is the last row of input.
+		let first_row = &buffer[BMP_INDEXED_HEADER_SIZE..][..WIDTH];
+		assert_eq!(first_row, [4, 5, 6, 7]);
+	}
+
+	#[test]
+	fn write_indexed_custom_palette() {
+		let mut palette = [[0u8; 3]; 256];
+		palette[9] = [10, 20, 30];
+
+		let pixels = [9u8];
+		let mut buffer = [0u8; buffer_length_indexed(1, 1)];
+		write_bmp_indexed(&mut buffer, 1, 1, &pixels, Some(&palette)).unwrap();
+
+		let entry = &buffer[54 + 9 * 4..][..4];
+		assert_eq!(entry, [30, 20, 10, 0]);
+	}
+
+	// A minimal RLE8 decoder used only to verify the encoder round-trips correctly.
+	// Panics on the delta escape, which this crate's encoder never emits.
+	fn decode_rle8(compressed: &[u8]) -> ([u8; 1024], usize) {
+		let mut out = [0u8; 1024];
+		let mut dst = 0;
+		let mut src = 0;
+
+		while src < compressed.len() {
+			let count = compressed[src];
+			let second = compressed[src + 1];
+			src += 2;
+
+			if count == 0 {
+				match second {
+					0 => continue,
+					1 => break,
+					2 => panic!("delta escape not used by this encoder"),
+					len => {
+						let len = len as usize;
+						out[dst..][..len].copy_from_slice(&compressed[src..][..len]);
+						dst += len;
+						src += len + (len % 2);
+					}
+				}
+			} else {
+				for _ in 0..count as usize {
+					out[dst] = second;
+					dst += 1;
+				}
+			}
+		}
+
+		(out, dst)
+	}
+
+	#[test]
+	fn rle_round_trip() {
+		const WIDTH: usize = 260;
+		const HEIGHT: usize = 2;
+
+		// A long run, a short run that shouldn't expand, and a literal tail.
+		let mut pixels = [7u8; WIDTH * HEIGHT];
+		for (index, pixel) in pixels[..6].iter_mut().enumerate() {
+			*pixel = index as u8;
+		}
+
+		let mut buffer = [0u8; buffer_length_indexed_rle_worst_case(WIDTH, HEIGHT)];
+		let file_length = write_bmp_indexed_rle(&mut buffer, WIDTH, HEIGHT, &pixels, None).unwrap();
+
+		let compression = u32::from_le_bytes(buffer[30..][..4].try_into().unwrap());
+		assert_eq!(compression, 1);
+
+		// Smaller than the uncompressed equivalent thanks to the long run of 7s.
+		assert!(file_length < buffer_length_indexed(WIDTH, HEIGHT));
+
+		let (decoded, decoded_len) = decode_rle8(&buffer[BMP_INDEXED_HEADER_SIZE..file_length]);
+		assert_eq!(decoded_len, WIDTH * HEIGHT);
+
+		let mut expected = [0u8; WIDTH * HEIGHT];
+		for row in 0..HEIGHT {
+			let src_row = HEIGHT - row - 1;
+			expected[row * WIDTH..][..WIDTH].copy_from_slice(&pixels[src_row * WIDTH..][..WIDTH]);
+		}
+		assert_eq!(&decoded[..decoded_len], &expected[..]);
+	}
+
+	#[test]
+	fn rle_decodes_single_pixel() {
+		let pixels = [5u8];
+		let mut buffer = [0u8; buffer_length_indexed_rle_worst_case(1, 1)];
+		let file_length = write_bmp_indexed_rle(&mut buffer, 1, 1, &pixels, None).unwrap();
+
+		let (decoded, decoded_len) = decode_rle8(&buffer[BMP_INDEXED_HEADER_SIZE..file_length]);
+		assert_eq!(&decoded[..decoded_len], &pixels[..]);
+	}
+
+	#[test]
+	fn rle_decodes_short_literal_run() {
+		const WIDTH: usize = 2;
+		const HEIGHT: usize = 1;
+
+		// Two distinct, non-repeating pixels: too short for absolute mode.
+		let pixels = [1u8, 2];
+		let mut buffer = [0u8; buffer_length_indexed_rle_worst_case(WIDTH, HEIGHT)];
+		let file_length = write_bmp_indexed_rle(&mut buffer, WIDTH, HEIGHT, &pixels, None).unwrap();
+
+		let (decoded, decoded_len) = decode_rle8(&buffer[BMP_INDEXED_HEADER_SIZE..file_length]);
+		assert_eq!(&decoded[..decoded_len], &pixels[..]);
+	}
+
+	#[test]
+	fn rle_encode_row_matches_input() {
+		let row = [1u8, 1, 1, 1, 2, 3, 4, 4, 4, 4, 4, 4];
+		let mut buffer = [0u8; 64];
+		let mut pos = 0;
+		rle_encode_row(&row, &mut buffer, &mut pos).unwrap();
+
+		// (4, 1), encoded pairs for the too-short literal run [2, 3], (6, 4)
+		assert_eq!(&buffer[..pos], &[4, 1, 1, 2, 1, 3, 6, 4]);
+
+		let (decoded, decoded_len) = decode_rle8(&buffer[..pos]);
+		assert_eq!(decoded_len, row.len());
+		assert_eq!(&decoded[..decoded_len], &row[..]);
+	}
+
+	#[test]
+	fn dimensions_overflow() {
+		assert_eq!(try_buffer_length(usize::MAX, usize::MAX), None);
+		assert_eq!(try_buffer_length_rgba(usize::MAX, usize::MAX), None);
+
+		let mut buffer = [0u8; 100];
+		let pixels = [0u8; 100];
+		let result = write_bmp(&mut buffer, usize::MAX, 2, &pixels);
+		assert_eq!(result, Err(Error::DimensionsOverflow { width: usize::MAX, height: 2 }));
+
+		let result = write_bmp_rgba(&mut buffer, usize::MAX, 2, &pixels);
+		assert_eq!(result, Err(Error::DimensionsOverflow { width: usize::MAX, height: 2 }));
+	}
+
+	#[test]
+	fn topdown_round_trip() {
+		const WIDTH: usize = 5;
+		const HEIGHT: usize = 9;
+
+		let mut pixels = [0u8; WIDTH * HEIGHT * 3];
+		for (index, pixel) in pixels.iter_mut().enumerate() {
+			*pixel = (index % 256) as u8;
+		}
+
+		let mut buffer = [0u8; buffer_length(WIDTH, HEIGHT)];
+		let file_length = write_bmp_topdown(&mut buffer, WIDTH, HEIGHT, &pixels).unwrap();
+
+		let stored_height = i32::from_le_bytes(buffer[22..][..4].try_into().unwrap());
+		assert_eq!(stored_height, -(HEIGHT as i32));
+
+		let mut out = [0u8; WIDTH * HEIGHT * 3];
+		let out_len = read_bmp_into(&buffer[..file_length], &mut out).unwrap();
+		assert_eq!(out_len, pixels.len());
+		assert_eq!(out, pixels);
+	}
+
+	#[test]
+	fn custom_resolution() {
+		let pixels = [0u8; 3];
+		let mut buffer = [0u8; buffer_length(1, 1)];
+		let resolution = Resolution::from_dpi(96);
+		write_bmp_with_resolution(&mut buffer, 1, 1, &pixels, resolution).unwrap();
+
+		let x_ppm = u32::from_le_bytes(buffer[38..][..4].try_into().unwrap());
+		let y_ppm = u32::from_le_bytes(buffer[42..][..4].try_into().unwrap());
+		assert_eq!(x_ppm, resolution.x_pixels_per_meter);
+		assert_eq!(y_ppm, resolution.y_pixels_per_meter);
+		assert_eq!(x_ppm, 3779);
+	}
+
+	#[test]
+	fn write_rgba() {
+		const WIDTH: usize = 4;
+		const HEIGHT: usize = 3;
+
+		let mut pixels = [0u8; WIDTH * HEIGHT * 4];
+		for (index, pixel) in pixels.iter_mut().enumerate() {
+			*pixel = (index % 256) as u8;
+		}
+
+		let mut buffer = [0u8; buffer_length_rgba(WIDTH, HEIGHT)];
+		let file_length = write_bmp_rgba(&mut buffer, WIDTH, HEIGHT, &pixels).unwrap();
+		assert_eq!(file_length, buffer.len());
+
+		let dib_header_size = u32::from_le_bytes(buffer[14..][..4].try_into().unwrap());
+		assert_eq!(dib_header_size, 108);
+
+		let compression = u32::from_le_bytes(buffer[30..][..4].try_into().unwrap());
+		assert_eq!(compression, 3);
+
+		let bit_count = u16::from_le_bytes(buffer[28..][..2].try_into().unwrap());
+		assert_eq!(bit_count, 32);
+
+		let alpha_mask = u32::from_le_bytes(buffer[66..][..4].try_into().unwrap());
+		assert_eq!(alpha_mask, 0xFF000000);
+
+		let first_row = &buffer[BMP_V4_HEADER_SIZE..][..WIDTH * 4];
+		let last_input_row = &pixels[(HEIGHT - 1) * WIDTH * 4..][..WIDTH * 4];
+		assert_eq!(first_row, last_input_row);
+	}
+
+	#[test]
+	fn rgba_bad_pixel_data_length() {
+		let mut buffer = [0u8; buffer_length_rgba(2, 2)];
+		let pixels = [0u8; 2 * 2 * 4 - 1];
+		let result = write_bmp_rgba(&mut buffer, 2, 2, &pixels);
+		assert_eq!(result, Err(Error::BadPixelDataLength { expected: 2 * 2 * 4, was: 2 * 2 * 4 - 1 }));
+	}
+
+	#[test]
+	fn round_trip_read_write() {
+		const WIDTH: usize = 13;
+		const HEIGHT: usize = 7;
+
+		let mut pixels = [0u8; WIDTH * HEIGHT * 3];
+		for (index, pixel) in pixels.iter_mut().enumerate() {
+			*pixel = (index % 256) as u8;
+		}
+
+		let mut buffer = [0u8; buffer_length(WIDTH, HEIGHT)];
+		let file_length = write_bmp(&mut buffer, WIDTH, HEIGHT, &pixels).unwrap();
+
+		let info = parse_header(&buffer[..file_length]).unwrap();
+		assert_eq!(info, BmpInfo { width: WIDTH, height: HEIGHT, bit_count: 24 });
+
+		let mut out = [0u8; WIDTH * HEIGHT * 3];
+		let out_len = read_bmp_into(&buffer[..file_length], &mut out).unwrap();
+		assert_eq!(out_len, pixels.len());
+		assert_eq!(out, pixels);
+	}
+
+	#[test]
+	fn not_bmp() {
+		let buffer = [0u8; BMP_HEADER_SIZE];
+		let mut out = [0u8; 3];
+		assert_eq!(parse_header(&buffer), Err(Error::NotBmp));
+		assert_eq!(read_bmp_into(&buffer, &mut out), Err(Error::NotBmp));
+	}
+
+	#[test]
+	fn unsupported_bit_depth() {
+		let mut buffer = [0u8; buffer_length(1, 1)];
+		write_bmp(&mut buffer, 1, 1, &[0u8; 3]).unwrap();
+		buffer[28..][..2].copy_from_slice(&8u16.to_le_bytes());
+
+		match parse_header(&buffer) {
+			Err(Error::UnsupportedBitDepth { was: 8 }) => {}
+			otherwise => panic!("Bit depth error is incorrect. {:?}", otherwise),
+		}
+	}
+
+	#[test]
+	fn unsupported_compression() {
+		let mut buffer = [0u8; buffer_length(1, 1)];
+		write_bmp(&mut buffer, 1, 1, &[0u8; 3]).unwrap();
+		buffer[30..][..4].copy_from_slice(&1u32.to_le_bytes());
+
+		match parse_header(&buffer) {
+			Err(Error::UnsupportedCompression { was: 1 }) => {}
+			otherwise => panic!("Compression error is incorrect. {:?}", otherwise),
+		}
+	}
 }